@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[clap(about, version)]
+pub struct Args {
+    #[clap(flatten)]
+    pub project_args: ProjectArgs,
+    /// Run this command against the Shuttle backend at this URL
+    #[clap(long, env = "SHUTTLE_API")]
+    pub api_url: Option<String>,
+    #[clap(subcommand)]
+    pub cmd: Command,
+}
+
+#[derive(Parser, Debug)]
+pub struct ProjectArgs {
+    /// Specify the working directory
+    #[clap(long, default_value = ".")]
+    pub working_directory: PathBuf,
+    /// Specify the name of the project (overrides crate name)
+    #[clap(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Deploy a shuttle project
+    Deploy(DeployArgs),
+    /// Manage deployments of a shuttle project
+    #[clap(subcommand)]
+    Deployment(DeploymentCommand),
+    /// Manage a shuttle project
+    #[clap(subcommand)]
+    Project(ProjectCommand),
+    /// Create a new shuttle project
+    Init(InitArgs),
+    /// View the status of a shuttle project
+    Status,
+    /// View the logs of a deployment
+    Logs {
+        /// ID of deployment to get logs for (defaults to currently running deployment)
+        id: Option<Uuid>,
+        /// Follow log output
+        #[clap(short, long)]
+        follow: bool,
+    },
+    /// Delete the latest deployment for a shuttle project
+    Delete,
+    /// Run a command inside the container of the running deployment
+    Exec(ExecArgs),
+    /// Load-test a deployed (or locally running) service
+    Bench(BenchArgs),
+    /// Manage secrets for a shuttle project
+    Secrets,
+    /// Run a shuttle project locally
+    Run(RunArgs),
+    /// Login to the shuttle platform
+    Login(LoginArgs),
+    /// Create a user
+    Auth(AuthArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DeploymentCommand {
+    /// List all the deployments for a service
+    List,
+    /// View status of a deployment
+    Status { id: Uuid },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectCommand {
+    /// Create a project on shuttle
+    New,
+    /// Check the status of the project on shuttle
+    Status,
+    /// Destroy the project on shuttle
+    Rm,
+}
+
+#[derive(Parser, Debug)]
+pub struct LoginArgs {
+    /// Api key for the shuttle platform
+    #[clap(long)]
+    pub api_key: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct AuthArgs {
+    /// The desired username
+    pub username: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct DeployArgs {
+    /// Allow dirty working directories to be packaged
+    #[clap(long)]
+    pub allow_dirty: bool,
+    /// Don't run pre-deploy tests
+    #[clap(long)]
+    pub no_test: bool,
+    /// Notify only the named `[notifiers]` target configured in Shuttle.toml when this deploy
+    /// finishes; may be given more than once (defaults to notifying all configured targets)
+    #[clap(long)]
+    pub notify: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunArgs {
+    /// Port to run the service on
+    #[clap(long, default_value = "8000")]
+    pub port: u16,
+    /// Expose the locally running service over a public https://*.shuttle.rs URL
+    #[clap(long)]
+    pub tunnel: bool,
+    /// Watch the project for source changes and automatically rebuild and reload
+    #[clap(long)]
+    pub watch: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ExecArgs {
+    /// ID of deployment to run the command in (defaults to currently running deployment)
+    #[clap(long)]
+    pub id: Option<Uuid>,
+    /// Allocate a pseudo-tty and put the local terminal into raw mode
+    #[clap(long)]
+    pub tty: bool,
+    /// The command (and its arguments) to run remotely, e.g. `-- ls -la`
+    #[clap(last = true, required = true)]
+    pub command: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct BenchArgs {
+    /// Base URL to load-test (defaults to the project's deployed URL)
+    #[clap(long)]
+    pub target: Option<String>,
+    /// Path to a TOML or JSON file describing the request(s) to send
+    #[clap(long)]
+    pub scenario: PathBuf,
+    /// Total number of requests to send
+    #[clap(long, default_value = "1000")]
+    pub requests: usize,
+    /// Number of requests to run concurrently
+    #[clap(long, default_value = "10")]
+    pub concurrency: usize,
+    /// Number of leading samples to discard as warmup
+    #[clap(long, default_value = "0")]
+    pub warmup: usize,
+    /// Folder to write the timestamped JSON report to
+    #[clap(long, default_value = "./bench/reports/")]
+    pub report_folder: PathBuf,
+    /// A prior report to compare percentiles against
+    #[clap(long)]
+    pub compare: Option<PathBuf>,
+    /// Fail if any percentile regresses by more than this many percent versus `--compare`
+    #[clap(long, default_value = "10.0")]
+    pub threshold: f64,
+    /// Fail the command if the error rate exceeds this percentage
+    #[clap(long, default_value = "1.0")]
+    pub error_ceiling: f64,
+}
+
+#[derive(Parser, Debug)]
+pub struct InitArgs {
+    /// Initialize with the given framework
+    #[clap(long)]
+    pub framework: Option<String>,
+    /// Path to initialize the project in
+    #[clap(default_value = ".")]
+    pub path: PathBuf,
+}
@@ -0,0 +1,290 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command as OsCommand;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::args::BenchArgs;
+use crate::CommandOutcome;
+
+/// A single request to fire at the target during a bench run, as read from
+/// the `--scenario` file.
+#[derive(Debug, Clone, Deserialize)]
+struct RequestDef {
+    method: String,
+    path: String,
+    #[serde(default)]
+    body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Scenario {
+    requests: Vec<RequestDef>,
+}
+
+fn load_scenario(path: &Path) -> Result<Scenario> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read scenario file {}", path.display()))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&contents).context("failed to parse scenario as json"),
+        _ => toml::from_str(&contents).context("failed to parse scenario as toml"),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchReport {
+    commit: String,
+    hostname: String,
+    timestamp_unix: u64,
+    count: usize,
+    rps: f64,
+    mean_ms: f64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    errors: BTreeMap<String, usize>,
+}
+
+/// Best-effort `git rev-parse` and hostname lookup, used to stamp a report
+/// with the environment it was produced in.
+fn env_info() -> (String, String) {
+    let commit = OsCommand::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let hostname = OsCommand::new("hostname")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    (commit, hostname)
+}
+
+/// Drop the first `warmup` samples by *completion order* (cold-start
+/// outliers) and sort the remainder for percentile math. `latencies` must be
+/// in the order requests actually completed in, not already sorted, or this
+/// would discard the fastest samples instead.
+fn discard_warmup_and_sort(latencies: Vec<Duration>, warmup: usize) -> Vec<Duration> {
+    let warmup = warmup.min(latencies.len());
+    let mut sample = latencies[warmup..].to_vec();
+    sample.sort();
+    sample
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> f64 {
+    let idx = ((p / 100.0) * sorted.len() as f64) as usize;
+    let idx = idx.min(sorted.len().saturating_sub(1));
+    sorted[idx].as_secs_f64() * 1000.0
+}
+
+async fn send_request(client: &reqwest::Client, target: &str, request: &RequestDef) -> Result<reqwest::StatusCode> {
+    let method = request.method.parse::<reqwest::Method>()?;
+    let url = format!("{}{}", target.trim_end_matches('/'), request.path);
+
+    let mut builder = client.request(method, url);
+    if let Some(body) = &request.body {
+        builder = builder.json(body);
+    }
+
+    Ok(builder.send().await?.status())
+}
+
+pub async fn run(args: &BenchArgs, target: &str) -> Result<CommandOutcome> {
+    let scenario = load_scenario(&args.scenario)?;
+    anyhow::ensure!(!scenario.requests.is_empty(), "scenario must define at least one request");
+
+    let client = reqwest::Client::new();
+    let counter = Arc::new(AtomicUsize::new(0));
+    let latencies = Arc::new(Mutex::new(Vec::with_capacity(args.requests)));
+    let errors = Arc::new(Mutex::new(BTreeMap::<String, usize>::new()));
+
+    let started_at = Instant::now();
+
+    let mut workers = Vec::with_capacity(args.concurrency);
+    for _ in 0..args.concurrency {
+        let client = client.clone();
+        let counter = counter.clone();
+        let latencies = latencies.clone();
+        let errors = errors.clone();
+        let requests = scenario.requests.clone();
+        let target = target.to_string();
+        let total = args.requests;
+
+        workers.push(tokio::spawn(async move {
+            loop {
+                let i = counter.fetch_add(1, Ordering::SeqCst);
+                if i >= total {
+                    break;
+                }
+
+                let request = &requests[i % requests.len()];
+                let started = Instant::now();
+
+                match send_request(&client, &target, request).await {
+                    Ok(status) if status.is_success() => {
+                        latencies.lock().unwrap().push(started.elapsed());
+                    }
+                    Ok(status) => {
+                        let class = format!("{}xx", status.as_u16() / 100);
+                        *errors.lock().unwrap().entry(class).or_insert(0) += 1;
+                    }
+                    Err(_) => {
+                        *errors.lock().unwrap().entry("error".to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }));
+    }
+
+    for worker in workers {
+        worker.await?;
+    }
+    let wall_time = started_at.elapsed();
+
+    let latencies = Arc::try_unwrap(latencies)
+        .expect("all workers to have finished")
+        .into_inner()
+        .unwrap();
+
+    let sample = discard_warmup_and_sort(latencies, args.warmup);
+    let sample = &sample[..];
+    let errors = Arc::try_unwrap(errors)
+        .expect("all workers to have finished")
+        .into_inner()
+        .unwrap();
+
+    let error_count: usize = errors.values().sum();
+    let error_rate = error_count as f64 / args.requests as f64 * 100.0;
+
+    let report = if sample.is_empty() {
+        anyhow::bail!("every request in the bench run failed, aborting before writing a report");
+    } else {
+        let total_time: Duration = sample.iter().sum();
+        let (commit, hostname) = env_info();
+
+        BenchReport {
+            commit,
+            hostname,
+            timestamp_unix: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            count: sample.len(),
+            rps: sample.len() as f64 / wall_time.as_secs_f64().max(f64::EPSILON),
+            mean_ms: total_time.as_secs_f64() * 1000.0 / sample.len() as f64,
+            p50_ms: percentile(sample, 50.0),
+            p90_ms: percentile(sample, 90.0),
+            p95_ms: percentile(sample, 95.0),
+            p99_ms: percentile(sample, 99.0),
+            errors,
+        }
+    };
+
+    fs::create_dir_all(&args.report_folder)?;
+    let report_path = args
+        .report_folder
+        .join(format!("bench-{}-{}.json", report.commit, report.timestamp_unix));
+    fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+
+    println!("Wrote bench report to {}", report_path.display());
+    print_report(&report);
+
+    let mut regressed = false;
+    if let Some(compare_path) = &args.compare {
+        let baseline: BenchReport =
+            serde_json::from_str(&fs::read_to_string(compare_path)?).context("failed to parse baseline report")?;
+        regressed = print_comparison(&baseline, &report, args.threshold);
+    }
+
+    if error_rate > args.error_ceiling || regressed {
+        return Ok(CommandOutcome::DeploymentFailure);
+    }
+
+    Ok(CommandOutcome::Ok)
+}
+
+fn print_report(report: &BenchReport) {
+    println!(
+        "count={} rps={:.1} mean={:.1}ms p50={:.1}ms p90={:.1}ms p95={:.1}ms p99={:.1}ms",
+        report.count, report.rps, report.mean_ms, report.p50_ms, report.p90_ms, report.p95_ms, report.p99_ms
+    );
+
+    for (class, count) in &report.errors {
+        println!("  {class}: {count}");
+    }
+}
+
+/// Print a before/after table for each percentile and report whether any of
+/// them regressed by more than `threshold` percent.
+fn print_comparison(baseline: &BenchReport, current: &BenchReport, threshold: f64) -> bool {
+    println!("\n{:<6} {:>10} {:>10} {:>10}", "", "baseline", "current", "delta");
+
+    let mut regressed = false;
+    for (label, base, cur) in [
+        ("p50", baseline.p50_ms, current.p50_ms),
+        ("p90", baseline.p90_ms, current.p90_ms),
+        ("p95", baseline.p95_ms, current.p95_ms),
+        ("p99", baseline.p99_ms, current.p99_ms),
+    ] {
+        let delta_pct = (cur - base) / base.max(f64::EPSILON) * 100.0;
+        let flag = if delta_pct > threshold {
+            regressed = true;
+            " <- regression"
+        } else {
+            ""
+        };
+
+        println!("{label:<6} {base:>9.1}ms {cur:>9.1}ms {delta_pct:>+9.1}%{flag}");
+    }
+
+    regressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn percentile_picks_the_right_bucket() {
+        let sorted = vec![ms(10), ms(20), ms(30), ms(40), ms(50)];
+
+        assert_eq!(percentile(&sorted, 0.0), 10.0);
+        assert_eq!(percentile(&sorted, 50.0), 30.0);
+        assert_eq!(percentile(&sorted, 99.0), 50.0);
+    }
+
+    #[test]
+    fn discard_warmup_drops_by_completion_order_not_by_speed() {
+        // Slow cold-start sample arrives first, fast ones after, out of
+        // numeric order: a sort-then-slice would keep this slow sample and
+        // drop a fast one instead of the other way around.
+        let latencies = vec![ms(500), ms(10), ms(20), ms(30)];
+
+        let sample = discard_warmup_and_sort(latencies, 1);
+
+        assert_eq!(sample, vec![ms(10), ms(20), ms(30)]);
+    }
+
+    #[test]
+    fn discard_warmup_clamps_to_the_sample_size() {
+        let latencies = vec![ms(10), ms(20)];
+
+        let sample = discard_warmup_and_sort(latencies, 10);
+
+        assert!(sample.is_empty());
+    }
+}
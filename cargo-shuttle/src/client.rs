@@ -0,0 +1,224 @@
+use std::fs::File;
+
+use anyhow::{Context, Result};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use shuttle_common::{project::Project, secret::Secret, DeploymentMeta, LogItem, ServiceInfo, User};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+/// A thin wrapper around `reqwest` that knows how to authenticate against,
+/// and talk the JSON protocol of, the Shuttle backend.
+pub struct Client {
+    api_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(api_url: String) -> Self {
+        Self {
+            api_url,
+            api_key: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn set_api_key(&mut self, api_key: String) {
+        self.api_key = Some(api_key);
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, format!("{}{path}", self.api_url));
+
+        match &self.api_key {
+            Some(api_key) => builder.bearer_auth(api_key),
+            None => builder,
+        }
+    }
+
+    async fn to_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+        let response = response.error_for_status()?;
+        Ok(response.json().await?)
+    }
+
+    async fn ws_url(&self, path: &str) -> Result<String> {
+        let url = format!("{}{path}", self.api_url);
+        Ok(url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1))
+    }
+
+    async fn connect_ws(
+        &self,
+        path: &str,
+    ) -> Result<WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>> {
+        let url = self.ws_url(path).await?;
+        let mut request = url.into_client_request()?;
+
+        if let Some(api_key) = &self.api_key {
+            request
+                .headers_mut()
+                .insert("Authorization", format!("Bearer {api_key}").parse()?);
+        }
+
+        let (stream, _) = connect_async(request).await.context("ws handshake failed")?;
+        Ok(stream)
+    }
+
+    /// Open the websocket used to run a command inside the container of a
+    /// running deployment. The caller is expected to send the command to run
+    /// as the first frame, then forward stdin as `Message::Binary` frames.
+    pub async fn exec_ws(
+        &self,
+        project_name: &str,
+        deployment_id: &Uuid,
+        tty: bool,
+    ) -> Result<WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>> {
+        self.connect_ws(&format!(
+            "/projects/{project_name}/deployments/{deployment_id}/exec/ws?tty={tty}"
+        ))
+        .await
+    }
+
+    pub async fn auth(&self, username: String) -> Result<User> {
+        let response = self
+            .request(reqwest::Method::POST, &format!("/users/{username}"))
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn deploy(
+        &self,
+        package_file: File,
+        project_name: &str,
+        no_test: bool,
+    ) -> Result<DeploymentMeta> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/projects/{project_name}/services/{project_name}?no-test={no_test}"),
+            )
+            .body(package_file)
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn delete_service(&self, project_name: &str) -> Result<ServiceInfo> {
+        let response = self
+            .request(
+                reqwest::Method::DELETE,
+                &format!("/projects/{project_name}/services/{project_name}"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_service_summary(&self, project_name: &str) -> Result<ServiceInfo> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{project_name}/services/{project_name}/summary"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_service_details(&self, project_name: &str) -> Result<ServiceInfo> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{project_name}/services/{project_name}"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_deployment_details(
+        &self,
+        project_name: &str,
+        deployment_id: &Uuid,
+    ) -> Result<DeploymentMeta> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{project_name}/deployments/{deployment_id}"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_secrets(&self, project_name: &str) -> Result<Vec<Secret>> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{project_name}/secrets"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_runtime_logs(&self, project_name: &str, deployment_id: &Uuid) -> Result<Vec<LogItem>> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{project_name}/deployments/{deployment_id}/logs"),
+            )
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_runtime_logs_ws(
+        &self,
+        project_name: &str,
+        deployment_id: &Uuid,
+    ) -> Result<impl Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>> {
+        self.connect_ws(&format!(
+            "/projects/{project_name}/deployments/{deployment_id}/logs/ws"
+        ))
+        .await
+    }
+
+    pub async fn get_build_logs_ws(
+        &self,
+        project_name: &str,
+        deployment_id: &Uuid,
+    ) -> Result<impl Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>> {
+        self.connect_ws(&format!(
+            "/projects/{project_name}/deployments/{deployment_id}/build-logs/ws"
+        ))
+        .await
+    }
+
+    pub async fn create_project(&self, project_name: &str) -> Result<Project> {
+        let response = self
+            .request(reqwest::Method::POST, &format!("/projects/{project_name}"))
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn get_project(&self, project_name: &str) -> Result<Project> {
+        let response = self
+            .request(reqwest::Method::GET, &format!("/projects/{project_name}"))
+            .send()
+            .await?;
+        Self::to_json(response).await
+    }
+
+    pub async fn delete_project(&self, project_name: &str) -> Result<()> {
+        self.request(reqwest::Method::DELETE, &format!("/projects/{project_name}"))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
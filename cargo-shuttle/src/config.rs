@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::args::ProjectArgs;
+use crate::notifier::NotifierConfig;
+
+/// Paths to `.rhai` scripts run around the deploy lifecycle, configured
+/// under a `[hooks]` table in `Shuttle.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HooksConfig {
+    pre_deploy: Option<PathBuf>,
+    post_deploy: Option<PathBuf>,
+}
+
+/// Global configuration, persisted under the user's config directory and
+/// shared across all projects (currently just the API key).
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GlobalConfig {
+    api_key: Option<String>,
+}
+
+/// Per-project configuration, persisted alongside the project's `Cargo.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocalConfig {
+    name: Option<String>,
+    #[serde(default)]
+    hooks: HooksConfig,
+    #[serde(default)]
+    notifiers: BTreeMap<String, NotifierConfig>,
+}
+
+/// Holds all the state needed to make a request: the working directory of
+/// the project being operated on, its name, and the credentials used to talk
+/// to the Shuttle backend.
+pub struct RequestContext {
+    global_config_path: PathBuf,
+    global: GlobalConfig,
+    local: LocalConfig,
+    working_directory: PathBuf,
+    project_name: String,
+    api_url: Option<String>,
+}
+
+impl RequestContext {
+    /// Load the global configuration from disk, falling back to defaults if
+    /// it does not yet exist. The file itself is only written by
+    /// [`RequestContext::set_api_key`].
+    pub fn load_global() -> Result<Self> {
+        let global_config_path = Self::global_config_path()?;
+
+        let global = if global_config_path.exists() {
+            let mut contents = String::new();
+            File::open(&global_config_path)?.read_to_string(&mut contents)?;
+
+            toml::from_str(&contents).context("failed to parse global config file")?
+        } else {
+            GlobalConfig::default()
+        };
+
+        Ok(Self {
+            global_config_path,
+            global,
+            local: LocalConfig::default(),
+            working_directory: PathBuf::new(),
+            project_name: String::new(),
+            api_url: None,
+        })
+    }
+
+    fn global_config_path() -> Result<PathBuf> {
+        let path = dirs::config_dir()
+            .context("failed to get config dir")?
+            .join("shuttle")
+            .join("config.toml");
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(path)
+    }
+
+    /// Load the local, project-scoped configuration based on the resolved
+    /// project directory.
+    pub fn load_local(&mut self, project_args: &ProjectArgs) -> Result<()> {
+        self.working_directory = project_args.working_directory.clone();
+
+        let local_config_path = self.working_directory.join("Shuttle.toml");
+        self.local = if local_config_path.exists() {
+            let contents = fs::read_to_string(&local_config_path)?;
+            toml::from_str(&contents).context("failed to parse Shuttle.toml")?
+        } else {
+            LocalConfig::default()
+        };
+
+        self.project_name = project_args
+            .name
+            .clone()
+            .or_else(|| self.local.name.clone())
+            .or_else(|| {
+                self.working_directory
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .context("could not determine a project name")?;
+
+        Ok(())
+    }
+
+    pub fn working_directory(&self) -> &std::path::Path {
+        self.working_directory.as_path()
+    }
+
+    pub fn pre_deploy_hook(&self) -> Option<PathBuf> {
+        self.resolve_hook(self.local.hooks.pre_deploy.as_deref())
+    }
+
+    pub fn post_deploy_hook(&self) -> Option<PathBuf> {
+        self.resolve_hook(self.local.hooks.post_deploy.as_deref())
+    }
+
+    fn resolve_hook(&self, path: Option<&Path>) -> Option<PathBuf> {
+        path.map(|path| self.working_directory.join(path))
+    }
+
+    pub fn notifiers(&self) -> &BTreeMap<String, NotifierConfig> {
+        &self.local.notifiers
+    }
+
+    pub fn project_name(&self) -> &String {
+        &self.project_name
+    }
+
+    pub fn set_api_url(&mut self, api_url: Option<String>) {
+        self.api_url = api_url;
+    }
+
+    pub fn api_url(&self) -> String {
+        self.api_url
+            .clone()
+            .unwrap_or_else(|| "https://api.shuttle.rs".to_string())
+    }
+
+    pub fn api_key(&self) -> Result<String> {
+        self.global
+            .api_key
+            .clone()
+            .context("unable to find an api key, try running `cargo shuttle login`")
+    }
+
+    pub fn set_api_key(&mut self, api_key: String) -> Result<()> {
+        self.global.api_key = Some(api_key);
+
+        let serialized = toml::to_string_pretty(&self.global)?;
+        File::create(&self.global_config_path)?.write_all(serialized.as_bytes())?;
+
+        Ok(())
+    }
+}
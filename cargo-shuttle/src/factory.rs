@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use shuttle_service::Factory;
+
+/// A [`Factory`] used when running a project locally, backed by the
+/// `Secrets.toml` file of the project rather than a provisioner service.
+pub struct LocalFactory {
+    project_name: String,
+    secrets: BTreeMap<String, String>,
+}
+
+impl LocalFactory {
+    pub fn new(project_name: String, secrets: BTreeMap<String, String>) -> Result<Self> {
+        Ok(Self {
+            project_name,
+            secrets,
+        })
+    }
+}
+
+#[async_trait]
+impl Factory for LocalFactory {
+    fn get_secrets(&mut self) -> &BTreeMap<String, String> {
+        &self.secrets
+    }
+
+    fn get_project_name(&self) -> &str {
+        &self.project_name
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use rhai::{Engine, EvalAltResult};
+
+/// The values a hook script can read about the deploy it is running around.
+pub struct HookContext {
+    pub project_name: String,
+    pub deployment_id: Option<String>,
+}
+
+/// Evaluate a `.rhai` lifecycle hook with the CLI context bound in as
+/// helper functions: `project_name()`, `deployment_id()`, `http_get(url)`,
+/// `env(key)` and `fail(msg)`.
+///
+/// Calling `fail` from the script, or any other script error, surfaces as
+/// an `Err` here so the caller can abort the deploy. The evaluation itself
+/// (including any `http_get` calls) is blocking, so it runs on a blocking
+/// thread rather than stalling the tokio executor.
+pub async fn run(script_path: &Path, ctx: HookContext) -> Result<()> {
+    let script_path = script_path.to_path_buf();
+
+    tokio::task::spawn_blocking(move || run_blocking(&script_path, &ctx))
+        .await
+        .context("hook script task panicked")?
+}
+
+fn run_blocking(script_path: &Path, ctx: &HookContext) -> Result<()> {
+    let mut engine = Engine::new();
+
+    let project_name = ctx.project_name.clone();
+    engine.register_fn("project_name", move || project_name.clone());
+
+    let deployment_id = ctx.deployment_id.clone().unwrap_or_default();
+    engine.register_fn("deployment_id", move || deployment_id.clone());
+
+    engine.register_fn("env", |key: String| std::env::var(&key).unwrap_or_default());
+
+    engine.register_fn("http_get", |url: String| -> String {
+        reqwest::blocking::get(&url)
+            .and_then(|response| response.text())
+            .unwrap_or_default()
+    });
+
+    engine.register_fn("fail", |msg: String| -> Result<(), Box<EvalAltResult>> {
+        Err(msg.into())
+    });
+
+    engine
+        .run_file(script_path.to_path_buf())
+        .map_err(|error| anyhow!("hook script {} failed: {error}", script_path.display()))
+}
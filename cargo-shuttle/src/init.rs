@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use crate::args::InitArgs;
+
+/// A supported starter framework for `cargo shuttle init`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framework {
+    Axum,
+    Actix,
+    Rocket,
+    Tide,
+    Poem,
+    None,
+}
+
+pub fn get_framework(args: &InitArgs) -> Framework {
+    match args.framework.as_deref() {
+        Some("axum") => Framework::Axum,
+        Some("actix-web") => Framework::Actix,
+        Some("rocket") => Framework::Rocket,
+        Some("tide") => Framework::Tide,
+        Some("poem") => Framework::Poem,
+        _ => Framework::None,
+    }
+}
+
+/// Shell out to `cargo init --lib` to scaffold a new library crate.
+pub fn cargo_init(path: PathBuf) -> Result<()> {
+    let status = Command::new("cargo")
+        .arg("init")
+        .arg("--lib")
+        .arg(&path)
+        .status()?;
+
+    if !status.success() {
+        return Err(anyhow!("`cargo init` failed for {}", path.display()));
+    }
+
+    Ok(())
+}
+
+/// Write the Shuttle `src/lib.rs` template for the chosen framework into a
+/// freshly initialized crate. `Cargo.toml` is left as `cargo init` wrote it;
+/// no dependencies are added.
+pub fn cargo_shuttle_init(path: PathBuf, framework: Framework) -> Result<()> {
+    let lib_rs = path.join("src").join("lib.rs");
+
+    let contents = match framework {
+        Framework::Axum => AXUM_TEMPLATE,
+        Framework::Actix => ACTIX_TEMPLATE,
+        Framework::Rocket => ROCKET_TEMPLATE,
+        Framework::Tide => TIDE_TEMPLATE,
+        Framework::Poem => POEM_TEMPLATE,
+        Framework::None => NONE_TEMPLATE,
+    };
+
+    std::fs::write(lib_rs, contents)?;
+
+    Ok(())
+}
+
+const AXUM_TEMPLATE: &str = r#"#[shuttle_service::main]
+async fn axum() -> shuttle_service::ShuttleAxum {
+    let router = axum::Router::new();
+
+    Ok(router.into())
+}
+"#;
+
+const ACTIX_TEMPLATE: &str = r#"#[shuttle_service::main]
+async fn actix_web() -> shuttle_service::ShuttleActixWeb {
+    todo!()
+}
+"#;
+
+const ROCKET_TEMPLATE: &str = r#"#[shuttle_service::main]
+async fn rocket() -> shuttle_service::ShuttleRocket {
+    let rocket = rocket::build();
+
+    Ok(rocket.into())
+}
+"#;
+
+const TIDE_TEMPLATE: &str = r#"#[shuttle_service::main]
+async fn tide() -> shuttle_service::ShuttleTide<()> {
+    let server = tide::new();
+
+    Ok(server.into())
+}
+"#;
+
+const POEM_TEMPLATE: &str = r#"#[shuttle_service::main]
+async fn poem() -> shuttle_service::ShuttlePoem<impl poem::Endpoint> {
+    let app = poem::Route::new();
+
+    Ok(app.into())
+}
+"#;
+
+const NONE_TEMPLATE: &str = "// A Shuttle service starts here. Run `cargo shuttle init --framework <name>` to scaffold one.\n";
@@ -1,9 +1,14 @@
 mod args;
+mod bench;
 mod client;
 pub mod config;
 mod factory;
+mod hooks;
 mod init;
 mod logger;
+mod notifier;
+mod tunnel;
+mod watcher;
 
 use std::collections::BTreeMap;
 use std::fs::{read_to_string, File};
@@ -15,17 +20,19 @@ use std::rc::Rc;
 
 use anyhow::{anyhow, Context, Result};
 pub use args::{Args, Command, DeployArgs, InitArgs, ProjectArgs, RunArgs};
-use args::{AuthArgs, LoginArgs};
+use args::{AuthArgs, BenchArgs, ExecArgs, LoginArgs};
 use cargo::core::resolver::CliFeatures;
 use cargo::core::Workspace;
 use cargo::ops::{PackageOpts, Packages};
 use cargo_metadata::Message;
 use config::RequestContext;
 use crossterm::style::Stylize;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use factory::LocalFactory;
-use futures::StreamExt;
+use futures::{SinkExt, StreamExt};
 use shuttle_common::{deployment, secret};
 use shuttle_service::loader::{build_crate, Loader};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
 use tracing::trace;
 use uuid::Uuid;
 
@@ -61,14 +68,23 @@ impl Shuttle {
                 | Command::Status
                 | Command::Logs { .. }
                 | Command::Run(..)
+                | Command::Exec(..)
+                | Command::Bench(..)
         ) {
             self.load_project(&mut args.project_args)?;
         }
 
         self.ctx.set_api_url(args.api_url);
 
+        // A `bench --target <url>` run talks to the target directly and
+        // never touches the shuttle API, so it shouldn't require the user
+        // to be logged in.
+        let needs_api_key = !matches!(&args.cmd, Command::Bench(bench_args) if bench_args.target.is_some());
+
         let mut client = Client::new(self.ctx.api_url());
-        client.set_api_key(self.ctx.api_key()?);
+        if needs_api_key {
+            client.set_api_key(self.ctx.api_key()?);
+        }
 
         match args.cmd {
             Command::Deploy(deploy_args) => {
@@ -77,6 +93,8 @@ impl Shuttle {
             Command::Init(init_args) => self.init(init_args).await,
             Command::Status => self.status(&client).await,
             Command::Logs { id, follow } => self.logs(&client, id, follow).await,
+            Command::Exec(exec_args) => self.exec(&client, exec_args).await,
+            Command::Bench(bench_args) => return self.bench(bench_args).await,
             Command::Deployment(DeploymentCommand::List) => self.deployments_list(&client).await,
             Command::Deployment(DeploymentCommand::Status { id }) => {
                 self.deployment_get(&client, id).await
@@ -227,6 +245,130 @@ impl Shuttle {
         Ok(())
     }
 
+    async fn exec(&self, client: &Client, exec_args: ExecArgs) -> Result<()> {
+        let id = if let Some(id) = exec_args.id {
+            id
+        } else {
+            let summary = client.get_service_summary(self.ctx.project_name()).await?;
+
+            if let Some(deployment) = summary.deployment {
+                deployment.id
+            } else {
+                return Err(anyhow!("could not automatically find a running deployment for '{}'. Try passing a deployment ID manually", self.ctx.project_name()));
+            }
+        };
+
+        let stream = client
+            .exec_ws(self.ctx.project_name(), &id, exec_args.tty)
+            .await?;
+        let (mut sink, mut stream) = stream.split();
+
+        sink.send(WsMessage::Text(serde_json::to_string(&exec_args.command)?))
+            .await?;
+
+        // Raw mode is restored as soon as this guard is dropped, including on
+        // an early return caused by an error below.
+        struct RawModeGuard(bool);
+        impl Drop for RawModeGuard {
+            fn drop(&mut self) {
+                if self.0 {
+                    let _ = disable_raw_mode();
+                }
+            }
+        }
+
+        if exec_args.tty {
+            enable_raw_mode()?;
+        }
+        let _raw_mode_guard = RawModeGuard(exec_args.tty);
+
+        if exec_args.tty {
+            // The remote pty otherwise starts at a default size and only
+            // hears about changes from here on, via the `Resize` events
+            // below.
+            if let Ok((cols, rows)) = crossterm::terminal::size() {
+                let resize = serde_json::json!({ "resize": { "cols": cols, "rows": rows } });
+                sink.send(WsMessage::Text(resize.to_string())).await?;
+            }
+        }
+
+        let (stdin_tx, mut stdin_rx) = tokio::sync::mpsc::channel::<WsMessage>(16);
+
+        if exec_args.tty {
+            std::thread::spawn(move || loop {
+                match crossterm::event::read() {
+                    Ok(crossterm::event::Event::Key(key)) => {
+                        if let Some(bytes) = key_event_to_bytes(key) {
+                            if stdin_tx.blocking_send(WsMessage::Binary(bytes)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(crossterm::event::Event::Resize(cols, rows)) => {
+                        let resize = serde_json::json!({ "resize": { "cols": cols, "rows": rows } });
+                        if stdin_tx
+                            .blocking_send(WsMessage::Text(resize.to_string()))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            });
+        } else {
+            std::thread::spawn(move || {
+                let mut stdin = io::stdin();
+                let mut buf = [0u8; 1024];
+                loop {
+                    match std::io::Read::read(&mut stdin, &mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if stdin_tx
+                                .blocking_send(WsMessage::Binary(buf[..n].to_vec()))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        let write_task = tokio::spawn(async move {
+            while let Some(message) = stdin_rx.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(Ok(msg)) = stream.next().await {
+            match msg {
+                WsMessage::Text(text) => print!("{text}"),
+                WsMessage::Binary(bytes) => stdout().write_all(&bytes)?,
+                WsMessage::Close(_) => break,
+                _ => {}
+            }
+            stdout().flush()?;
+        }
+
+        write_task.abort();
+
+        Ok(())
+    }
+
+    async fn bench(&self, bench_args: BenchArgs) -> Result<CommandOutcome> {
+        let target = bench_args
+            .target
+            .clone()
+            .unwrap_or_else(|| format!("https://{}.shuttle.rs", self.ctx.project_name()));
+
+        bench::run(&bench_args, &target).await
+    }
+
     async fn deployments_list(&self, client: &Client) -> Result<()> {
         let details = client.get_service_details(self.ctx.project_name()).await?;
 
@@ -245,9 +387,11 @@ impl Shuttle {
         Ok(())
     }
 
-    async fn local_run(&self, run_args: RunArgs) -> Result<()> {
-        trace!("starting a local run for a service: {run_args:?}");
-
+    /// Compile the crate at `working_directory` into a loadable `.so`. This
+    /// is kept separate from [`Shuttle::load_built_crate`] so a failed
+    /// recompile (e.g. during `--watch`) can be detected before the
+    /// currently running instance is torn down.
+    async fn build_only(&self, working_directory: &Path) -> Result<PathBuf> {
         let (tx, rx): (crossbeam_channel::Sender<Message>, _) = crossbeam_channel::bounded(0);
         tokio::spawn(async move {
             while let Ok(message) = rx.recv() {
@@ -263,16 +407,25 @@ impl Shuttle {
             }
         });
 
-        let working_directory = self.ctx.working_directory();
-
         trace!("building project");
         println!(
             "{:>12} {}",
             "Building".bold().green(),
             working_directory.display()
         );
-        let so_path = build_crate(working_directory, tx).await?;
 
+        build_crate(working_directory, tx).await
+    }
+
+    /// Load an already-built `.so` and bind it to `addr`. Must only be
+    /// called once the port is free, i.e. after any previous instance
+    /// bound to the same `addr` has been aborted and closed.
+    async fn load_built_crate(
+        &self,
+        working_directory: &Path,
+        so_path: PathBuf,
+        addr: SocketAddr,
+    ) -> Result<(tokio::task::JoinHandle<Result<()>>, Box<dyn FnOnce() + Send>)> {
         trace!("loading secrets");
         let secrets_path = working_directory.join("Secrets.toml");
 
@@ -290,9 +443,7 @@ impl Shuttle {
             };
 
         let loader = Loader::from_so_file(so_path)?;
-
         let mut factory = LocalFactory::new(self.ctx.project_name().clone(), secrets)?;
-        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), run_args.port);
 
         trace!("loading project");
         println!(
@@ -304,17 +455,115 @@ impl Shuttle {
         let logger = Box::new(Logger::new());
         let (handle, so) = loader.load(&mut factory, addr, logger).await?;
 
-        handle.await??;
-
-        tokio::spawn(async move {
+        let close_so = Box::new(move || {
             trace!("closing so file");
             so.close().unwrap();
         });
 
-        Ok(())
+        Ok((handle, close_so))
+    }
+
+    /// Build the crate at `working_directory` and load the resulting `.so`
+    /// into a fresh service bound to `addr`. The returned closure closes the
+    /// loaded `.so` and is type-erased so callers don't need to know the
+    /// concrete handle `shuttle_service` hands back.
+    async fn build_and_load(
+        &self,
+        working_directory: &Path,
+        addr: SocketAddr,
+    ) -> Result<(tokio::task::JoinHandle<Result<()>>, Box<dyn FnOnce() + Send>)> {
+        let so_path = self.build_only(working_directory).await?;
+        self.load_built_crate(working_directory, so_path, addr)
+            .await
+    }
+
+    async fn local_run(&self, run_args: RunArgs) -> Result<()> {
+        trace!("starting a local run for a service: {run_args:?}");
+
+        let working_directory = self.ctx.working_directory().to_path_buf();
+        let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), run_args.port);
+
+        let (mut handle, mut close_so) = self.build_and_load(&working_directory, addr).await?;
+
+        if run_args.tunnel {
+            let public_url = tunnel::open(
+                &self.ctx.api_url(),
+                self.ctx.project_name(),
+                addr,
+                &self.ctx.api_key()?,
+            )
+            .await?;
+            println!("{:>12} {public_url}", "Tunnel".bold().green());
+        }
+
+        if !run_args.watch {
+            handle.await??;
+            tokio::spawn(async move { close_so() });
+
+            return Ok(());
+        }
+
+        let mut changes = watcher::watch(&working_directory)?;
+
+        loop {
+            tokio::select! {
+                result = &mut handle => {
+                    return result?;
+                }
+                Some(()) = changes.recv() => {
+                    println!(
+                        "\n{:>12} change detected, rebuilding",
+                        "Watching".bold().green()
+                    );
+
+                    match self.build_only(&working_directory).await {
+                        Ok(so_path) => {
+                            // Only tear down the running instance once the
+                            // rebuild has succeeded, and before loading the
+                            // new one, so the port is free when `load` binds
+                            // to `addr` again.
+                            handle.abort();
+                            let _ = (&mut handle).await;
+                            let old_close_so = close_so;
+                            old_close_so();
+
+                            match self.load_built_crate(&working_directory, so_path, addr).await {
+                                Ok((new_handle, new_close_so)) => {
+                                    close_so = new_close_so;
+                                    handle = new_handle;
+                                }
+                                Err(error) => {
+                                    println!("{:>12} {error}", "Load failed".bold().red());
+                                    return Err(error);
+                                }
+                            }
+                        }
+                        Err(error) => {
+                            println!("{:>12} {error}", "Build failed".bold().red());
+                            println!(
+                                "{:>12} keeping previous build up",
+                                "Watching".bold().green()
+                            );
+                        }
+                    }
+                }
+            }
+        }
     }
 
     async fn deploy(&self, args: DeployArgs, client: &Client) -> Result<CommandOutcome> {
+        if let Some(script) = self.ctx.pre_deploy_hook() {
+            let hook_ctx = hooks::HookContext {
+                project_name: self.ctx.project_name().clone(),
+                deployment_id: None,
+            };
+
+            if let Err(error) = hooks::run(&script, hook_ctx).await {
+                println!("{:>12} {error}", "PreDeploy".bold().red());
+                return Ok(CommandOutcome::DeploymentFailure);
+            }
+        }
+
         let package_file = self
             .run_cargo_package(args.allow_dirty)
             .context("failed to package cargo project")?;
@@ -345,22 +594,64 @@ impl Shuttle {
                     "Deployment has not entered the running state so kept previous deployment up"
                 );
 
+                self.notify_deploy(&args, &deployment.id, "not_running").await;
                 return Ok(CommandOutcome::DeploymentFailure);
             }
 
             println!("{service}");
 
-            Ok(match new_deployment.state {
-                deployment::State::Crashed => CommandOutcome::DeploymentFailure,
-                _ => CommandOutcome::Ok,
-            })
+            if matches!(new_deployment.state, deployment::State::Crashed) {
+                self.notify_deploy(&args, &deployment.id, "crashed").await;
+                return Ok(CommandOutcome::DeploymentFailure);
+            }
+
+            if let Some(script) = self.ctx.post_deploy_hook() {
+                let hook_ctx = hooks::HookContext {
+                    project_name: self.ctx.project_name().clone(),
+                    deployment_id: Some(new_deployment.id.to_string()),
+                };
+
+                if let Err(error) = hooks::run(&script, hook_ctx).await {
+                    println!("{:>12} {error}", "PostDeploy".bold().red());
+                    self.notify_deploy(&args, &deployment.id, "crashed").await;
+                    return Ok(CommandOutcome::DeploymentFailure);
+                }
+            }
+
+            self.notify_deploy(&args, &deployment.id, "running").await;
+            Ok(CommandOutcome::Ok)
         } else {
             println!("Deployment has not entered the running state");
 
+            self.notify_deploy(&args, &deployment.id, "not_running").await;
             Ok(CommandOutcome::DeploymentFailure)
         }
     }
 
+    /// Post a structured deploy event to every configured (or `--notify`
+    /// selected) notification target. Failures to notify are logged but do
+    /// not affect the deploy's outcome.
+    async fn notify_deploy(&self, args: &DeployArgs, deployment_id: &Uuid, state: &str) {
+        let notifiers = notifier::resolve(self.ctx.notifiers(), &args.notify);
+        if notifiers.is_empty() {
+            return;
+        }
+
+        let event = notifier::DeployEvent {
+            project_name: self.ctx.project_name().clone(),
+            deployment_id: deployment_id.to_string(),
+            state: state.to_string(),
+            build_log_url: format!(
+                "{}/projects/{}/deployments/{}/build-logs",
+                self.ctx.api_url(),
+                self.ctx.project_name(),
+                deployment_id
+            ),
+        };
+
+        notifier::dispatch(&notifiers, &event).await;
+    }
+
     async fn project_create(&self, client: &Client) -> Result<()> {
         let project = client.create_project(self.ctx.project_name()).await?;
 
@@ -416,6 +707,53 @@ impl Shuttle {
     }
 }
 
+/// Translate a key press captured in raw mode into the bytes a remote shell
+/// would expect to receive on its stdin.
+fn key_event_to_bytes(key: crossterm::event::KeyEvent) -> Option<Vec<u8>> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        if let KeyCode::Char(c) = key.code {
+            // Ctrl+<letter> maps to the control code 1-26 (e.g. Ctrl-C is
+            // 0x03, the byte a remote shell reads as SIGINT).
+            let upper = c.to_ascii_uppercase();
+            if upper.is_ascii_alphabetic() {
+                return Some(vec![upper as u8 - b'A' + 1]);
+            }
+        }
+    }
+
+    match key.code {
+        KeyCode::Char(c) => Some(c.to_string().into_bytes()),
+        KeyCode::Enter => Some(b"\r".to_vec()),
+        KeyCode::Tab => Some(b"\t".to_vec()),
+        KeyCode::Backspace => Some(vec![0x7f]),
+        KeyCode::Esc => Some(vec![0x1b]),
+        KeyCode::Up => Some(b"\x1b[A".to_vec()),
+        KeyCode::Down => Some(b"\x1b[B".to_vec()),
+        KeyCode::Right => Some(b"\x1b[C".to_vec()),
+        KeyCode::Left => Some(b"\x1b[D".to_vec()),
+        KeyCode::Home => Some(b"\x1b[H".to_vec()),
+        KeyCode::End => Some(b"\x1b[F".to_vec()),
+        KeyCode::F(n @ 1..=4) => Some(format!("\x1bO{}", (b'P' + (n - 1)) as char).into_bytes()),
+        KeyCode::F(n) => {
+            let code = match n {
+                5 => 15,
+                6 => 17,
+                7 => 18,
+                8 => 19,
+                9 => 20,
+                10 => 21,
+                11 => 23,
+                12 => 24,
+                _ => return None,
+            };
+            Some(format!("\x1b[{code}~").into_bytes())
+        }
+        _ => None,
+    }
+}
+
 pub enum CommandOutcome {
     Ok,
     DeploymentFailure,
@@ -424,7 +762,7 @@ pub enum CommandOutcome {
 #[cfg(test)]
 mod tests {
     use crate::args::ProjectArgs;
-    use crate::Shuttle;
+    use crate::{key_event_to_bytes, Shuttle};
     use std::path::PathBuf;
 
     fn path_from_workspace_root(path: &str) -> PathBuf {
@@ -460,4 +798,76 @@ mod tests {
             path_from_workspace_root("examples/axum/hello-world/")
         );
     }
+
+    fn key(code: crossterm::event::KeyCode) -> crossterm::event::KeyEvent {
+        crossterm::event::KeyEvent::new(code, crossterm::event::KeyModifiers::NONE)
+    }
+
+    fn ctrl_key(code: crossterm::event::KeyCode) -> crossterm::event::KeyEvent {
+        crossterm::event::KeyEvent::new(code, crossterm::event::KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn ctrl_c_sends_sigint_byte() {
+        use crossterm::event::KeyCode;
+
+        assert_eq!(
+            key_event_to_bytes(ctrl_key(KeyCode::Char('c'))),
+            Some(vec![0x03])
+        );
+    }
+
+    #[test]
+    fn ctrl_d_and_ctrl_z_send_their_control_bytes() {
+        use crossterm::event::KeyCode;
+
+        assert_eq!(
+            key_event_to_bytes(ctrl_key(KeyCode::Char('d'))),
+            Some(vec![0x04])
+        );
+        assert_eq!(
+            key_event_to_bytes(ctrl_key(KeyCode::Char('z'))),
+            Some(vec![0x1a])
+        );
+    }
+
+    #[test]
+    fn plain_char_is_unaffected_by_ctrl_handling() {
+        use crossterm::event::KeyCode;
+
+        assert_eq!(
+            key_event_to_bytes(key(KeyCode::Char('a'))),
+            Some(b"a".to_vec())
+        );
+    }
+
+    #[test]
+    fn cursor_keys_map_to_ansi_escape_sequences() {
+        use crossterm::event::KeyCode;
+
+        assert_eq!(key_event_to_bytes(key(KeyCode::Up)), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key_event_to_bytes(key(KeyCode::Down)), Some(b"\x1b[B".to_vec()));
+        assert_eq!(key_event_to_bytes(key(KeyCode::Left)), Some(b"\x1b[D".to_vec()));
+        assert_eq!(key_event_to_bytes(key(KeyCode::Right)), Some(b"\x1b[C".to_vec()));
+        assert_eq!(key_event_to_bytes(key(KeyCode::Home)), Some(b"\x1b[H".to_vec()));
+        assert_eq!(key_event_to_bytes(key(KeyCode::End)), Some(b"\x1b[F".to_vec()));
+    }
+
+    #[test]
+    fn function_keys_map_to_their_escape_sequences() {
+        use crossterm::event::KeyCode;
+
+        assert_eq!(
+            key_event_to_bytes(key(KeyCode::F(1))),
+            Some(b"\x1bOP".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(key(KeyCode::F(5))),
+            Some(b"\x1b[15~".to_vec())
+        );
+        assert_eq!(
+            key_event_to_bytes(key(KeyCode::F(12))),
+            Some(b"\x1b[24~".to_vec())
+        );
+    }
 }
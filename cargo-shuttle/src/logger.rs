@@ -0,0 +1,23 @@
+use shuttle_service::logger::Log;
+
+/// Forwards log records emitted by a locally loaded service to stdout,
+/// mirroring the format used by the deployed runtime.
+pub struct Logger;
+
+impl Logger {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Log for Logger {
+    fn log(&self, record: &str) {
+        println!("{record}");
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// A single notification target, configured under `[notifiers.<name>]` in
+/// `Shuttle.toml`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NotifierConfig {
+    /// Post the event as JSON to an arbitrary webhook.
+    Webhook { url: String },
+    /// Post a human-readable summary to a Slack incoming webhook.
+    Slack { webhook_url: String },
+}
+
+/// Project name, deployment id, final state, and build-log URL for a deploy
+/// that just finished, sent to every configured notification target.
+#[derive(Debug, Serialize)]
+pub struct DeployEvent {
+    pub project_name: String,
+    pub deployment_id: String,
+    pub state: String,
+    pub build_log_url: String,
+}
+
+impl DeployEvent {
+    fn summary(&self) -> String {
+        format!(
+            "Shuttle deploy of `{}` ({}): {} - {}",
+            self.project_name, self.deployment_id, self.state, self.build_log_url
+        )
+    }
+}
+
+#[async_trait]
+pub trait Notifier {
+    async fn notify(&self, event: &DeployEvent) -> Result<()>;
+}
+
+struct WebhookNotifier {
+    url: String,
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, event: &DeployEvent) -> Result<()> {
+        reqwest::Client::new()
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+struct SlackNotifier {
+    webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn notify(&self, event: &DeployEvent) -> Result<()> {
+        reqwest::Client::new()
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "text": event.summary() }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// Build the notifiers that should fire for this deploy: every configured
+/// target, or only those named by `--notify` if any were given.
+pub fn resolve(
+    configured: &BTreeMap<String, NotifierConfig>,
+    ad_hoc: &[String],
+) -> Vec<Box<dyn Notifier + Send + Sync>> {
+    for name in ad_hoc {
+        if !configured.contains_key(name) {
+            warn!(name, "--notify target has no matching [notifiers.*] entry, skipping");
+        }
+    }
+
+    configured
+        .iter()
+        .filter(|(name, _)| ad_hoc.is_empty() || ad_hoc.contains(name))
+        .map(|(_, config)| match config {
+            NotifierConfig::Webhook { url } => {
+                Box::new(WebhookNotifier { url: url.clone() }) as Box<dyn Notifier + Send + Sync>
+            }
+            NotifierConfig::Slack { webhook_url } => Box::new(SlackNotifier {
+                webhook_url: webhook_url.clone(),
+            }) as Box<dyn Notifier + Send + Sync>,
+        })
+        .collect()
+}
+
+/// Dispatch the event to every notifier, logging (but not propagating)
+/// individual failures so one bad webhook doesn't mask the deploy result.
+pub async fn dispatch(notifiers: &[Box<dyn Notifier + Send + Sync>], event: &DeployEvent) {
+    for notifier in notifiers {
+        if let Err(error) = notifier.notify(event).await {
+            warn!(%error, "failed to dispatch deploy notification");
+        }
+    }
+}
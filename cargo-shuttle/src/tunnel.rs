@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tracing::warn;
+
+type ControlStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[derive(Debug, Deserialize)]
+struct Registered {
+    subdomain: String,
+}
+
+/// Open a persistent control connection to the Shuttle backend, register a
+/// subdomain, and spawn a background task that proxies every multiplexed
+/// stream it receives to `local_addr`, reconnecting with backoff on drop.
+///
+/// Returns the public URL that now forwards to the local service.
+pub async fn open(
+    api_url: &str,
+    project_name: &str,
+    local_addr: SocketAddr,
+    api_key: &str,
+) -> Result<String> {
+    let control_url = to_ws_url(api_url, project_name);
+    let (mut control, public_url) = connect(&control_url, api_key).await?;
+    let api_key = api_key.to_string();
+
+    tokio::spawn(async move {
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            if let Err(error) = pump(&mut control, local_addr).await {
+                warn!(%error, "tunnel control connection dropped, reconnecting");
+            }
+
+            tokio::time::sleep(backoff).await;
+
+            match connect(&control_url, &api_key).await {
+                Ok((new_control, _)) => {
+                    control = new_control;
+                    backoff = Duration::from_secs(1);
+                }
+                Err(error) => {
+                    warn!(%error, "failed to reconnect tunnel");
+                    backoff = (backoff * 2).min(Duration::from_secs(30));
+                }
+            }
+        }
+    });
+
+    Ok(public_url)
+}
+
+fn to_ws_url(api_url: &str, project_name: &str) -> String {
+    let ws_url = api_url
+        .replacen("https://", "wss://", 1)
+        .replacen("http://", "ws://", 1);
+
+    format!("{ws_url}/projects/{project_name}/tunnel")
+}
+
+async fn connect(control_url: &str, api_key: &str) -> Result<(ControlStream, String)> {
+    let mut request = control_url.into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Authorization", format!("Bearer {api_key}").parse()?);
+
+    let (mut control, _) = connect_async(request)
+        .await
+        .context("failed to open tunnel control connection")?;
+
+    let registered = match control.next().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<Registered>(&text)
+            .context("failed to parse tunnel registration")?,
+        _ => anyhow::bail!("tunnel backend did not register a subdomain"),
+    };
+
+    Ok((control, format!("https://{}.shuttle.rs", registered.subdomain)))
+}
+
+/// Each multiplexed stream is framed on the control connection as a 4-byte
+/// big-endian stream id followed by its payload; an empty payload signals
+/// that the stream has closed.
+async fn pump(control: &mut ControlStream, local_addr: SocketAddr) -> Result<()> {
+    let mut streams: HashMap<u32, mpsc::Sender<Vec<u8>>> = HashMap::new();
+    let (events_tx, mut events_rx) = mpsc::channel::<(u32, Vec<u8>)>(64);
+
+    loop {
+        tokio::select! {
+            frame = control.next() => {
+                match frame {
+                    Some(Ok(Message::Binary(bytes))) if bytes.len() >= 4 => {
+                        let stream_id = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+                        let payload = bytes[4..].to_vec();
+
+                        if let Some(sender) = streams.get(&stream_id) {
+                            if sender.send(payload).await.is_err() {
+                                streams.remove(&stream_id);
+                            }
+                        } else if !payload.is_empty() {
+                            // A failed connect only kills this one stream,
+                            // not the whole control connection: every other
+                            // in-flight stream (and the tunnel itself)
+                            // should keep running.
+                            match spawn_local_connection(stream_id, payload, local_addr, events_tx.clone()).await {
+                                Ok(sender) => {
+                                    streams.insert(stream_id, sender);
+                                }
+                                Err(error) => {
+                                    warn!(%error, stream_id, "failed to open local connection for tunnel stream");
+                                }
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Err(error)) => return Err(error.into()),
+                    _ => {}
+                }
+            }
+            Some((stream_id, payload)) = events_rx.recv() => {
+                let mut framed = stream_id.to_be_bytes().to_vec();
+                framed.extend(payload);
+                control.send(Message::Binary(framed)).await?;
+            }
+        }
+    }
+}
+
+/// Open a fresh connection to the local service for a newly seen stream id
+/// and relay bytes in both directions until either side closes.
+async fn spawn_local_connection(
+    stream_id: u32,
+    first_payload: Vec<u8>,
+    local_addr: SocketAddr,
+    events_tx: mpsc::Sender<(u32, Vec<u8>)>,
+) -> Result<mpsc::Sender<Vec<u8>>> {
+    let mut socket = TcpStream::connect(local_addr)
+        .await
+        .context("failed to connect to local service for tunnel")?;
+    socket.write_all(&first_payload).await?;
+
+    let (to_local_tx, mut to_local_rx) = mpsc::channel::<Vec<u8>>(64);
+
+    tokio::spawn(async move {
+        let (mut read_half, mut write_half) = socket.into_split();
+        let mut buf = [0u8; 4096];
+
+        loop {
+            tokio::select! {
+                result = read_half.read(&mut buf) => {
+                    match result {
+                        Ok(0) | Err(_) => {
+                            let _ = events_tx.send((stream_id, Vec::new())).await;
+                            break;
+                        }
+                        Ok(n) => {
+                            if events_tx.send((stream_id, buf[..n].to_vec())).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                Some(payload) = to_local_rx.recv() => {
+                    if payload.is_empty() || write_half.write_all(&payload).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(to_local_tx)
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use ignore::gitignore::Gitignore;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+/// How long to wait for a burst of filesystem events to settle before
+/// treating it as a single change.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watch `root` recursively for source changes, skipping anything ignored
+/// by the project's `.gitignore` and always excluding `target/`.
+///
+/// Returns a channel that yields `()` once per settled burst of changes.
+pub fn watch(root: &Path) -> Result<mpsc::Receiver<()>> {
+    let (gitignore, _) = Gitignore::new(root.join(".gitignore"));
+    let target_dir = root.join("target");
+
+    let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let _ = raw_tx.send(event);
+        })?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+
+    let (tx, rx) = mpsc::channel(1);
+
+    std::thread::spawn(move || {
+        // Keep the watcher alive for as long as this thread is running.
+        let _watcher = watcher;
+
+        while let Ok(event) = raw_rx.recv() {
+            if !is_relevant(&event, &target_dir, &gitignore) {
+                continue;
+            }
+
+            // Drain any further events that arrive within the debounce
+            // window so a burst of saves collapses into one reload.
+            while raw_rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            if tx.blocking_send(()).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+fn is_relevant(event: &notify::Result<notify::Event>, target_dir: &Path, gitignore: &Gitignore) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+
+    event.paths.iter().any(|path| {
+        !path.starts_with(target_dir) && !gitignore.matched(path, path.is_dir()).is_ignore()
+    })
+}